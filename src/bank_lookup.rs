@@ -0,0 +1,154 @@
+//! Resilient client for the external IFSC validation API: bounded timeouts, retries with
+//! backoff + jitter on transient failures, and a TTL cache so repeat lookups skip the network.
+
+use crate::AppError;
+use dashmap::DashMap;
+use rand::Rng;
+use reqwest::Client as ReqwestClient;
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use tracing::{error, warn};
+
+const IFSC_VALIDATION_URL: &str = "https://api.bulkpe.in/api/validateIFSCStatic";
+const BASE_BACKOFF_MS: u64 = 100;
+const JITTER_MS: u64 = 100;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BankApiData {
+    pub bank_name: String,
+    pub bank_branch_name: String,
+    pub address: String,
+    pub city_and_pincode: String,
+    pub country_code: String,
+    pub network_type: String,
+    pub routing_no: String,
+    pub state_code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BankApiResponse {
+    data: BankApiData,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BankLookupSettings {
+    pub connect_timeout_ms: u64,
+    pub request_timeout_ms: u64,
+    pub max_retries: u32,
+    pub cache_ttl_secs: u64,
+}
+
+struct CacheEntry {
+    data: BankApiData,
+    cached_at: Instant,
+}
+
+pub type BankLookupCache = DashMap<String, CacheEntry>;
+
+/// Builds the `reqwest` client used for IFSC lookups, with connect + request timeouts applied.
+pub fn build_client(settings: &BankLookupSettings) -> Result<ReqwestClient, reqwest::Error> {
+    ReqwestClient::builder()
+        .connect_timeout(Duration::from_millis(settings.connect_timeout_ms))
+        .timeout(Duration::from_millis(settings.request_timeout_ms))
+        .build()
+}
+
+/// Whether a failed lookup attempt is worth retrying.
+enum LookupFailure {
+    Transient(AppError),
+    Fatal(AppError),
+}
+
+/// Looks up bank details for `ifsc`, serving from the TTL cache when possible and retrying
+/// transient upstream failures (connection errors, 5xx) with exponential backoff + jitter.
+pub async fn lookup_ifsc(
+    client: &ReqwestClient,
+    cache: &BankLookupCache,
+    settings: &BankLookupSettings,
+    ifsc: &str,
+) -> Result<BankApiData, AppError> {
+    if let Some(entry) = cache.get(ifsc) {
+        if entry.cached_at.elapsed() < Duration::from_secs(settings.cache_ttl_secs) {
+            return Ok(entry.data.clone());
+        }
+    }
+
+    let mut attempt = 0;
+    let data = loop {
+        attempt += 1;
+        match fetch_ifsc(client, ifsc).await {
+            Ok(data) => break data,
+            Err(LookupFailure::Fatal(e)) => return Err(e),
+            Err(LookupFailure::Transient(e)) => {
+                if attempt > settings.max_retries {
+                    return Err(e);
+                }
+                let backoff_ms = BASE_BACKOFF_MS * 2u64.pow(attempt - 1);
+                let jitter_ms = rand::thread_rng().gen_range(0..JITTER_MS);
+                warn!(
+                    "Transient IFSC lookup failure, retry {}/{} in {}ms: {}",
+                    attempt,
+                    settings.max_retries,
+                    backoff_ms + jitter_ms,
+                    e
+                );
+                tokio::time::sleep(Duration::from_millis(backoff_ms + jitter_ms)).await;
+            }
+        }
+    };
+
+    cache.insert(
+        ifsc.to_string(),
+        CacheEntry {
+            data: data.clone(),
+            cached_at: Instant::now(),
+        },
+    );
+
+    Ok(data)
+}
+
+async fn fetch_ifsc(client: &ReqwestClient, ifsc: &str) -> Result<BankApiData, LookupFailure> {
+    let response = client
+        .post(IFSC_VALIDATION_URL)
+        .json(&serde_json::json!({ "ifsc": ifsc }))
+        .send()
+        .await
+        .map_err(|e| {
+            error!("External API call failed. Full error: {:?}", e);
+            LookupFailure::Transient(AppError::Reqwest(e))
+        })?;
+
+    if response.status().is_server_error() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        error!(
+            "External API returned a server error: {}. Body: {}",
+            status, body
+        );
+        return Err(LookupFailure::Transient(AppError::UpstreamUnavailable(
+            "The bank lookup service is temporarily unavailable.".to_string(),
+        )));
+    }
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        error!(
+            "External API returned a non-success status: {}. Body: {}",
+            status, body
+        );
+        return Err(LookupFailure::Fatal(AppError::Validation(
+            "The provided IFSC code is not valid or could not be verified by the bank API."
+                .to_string(),
+        )));
+    }
+
+    let bank_response: BankApiResponse = response.json().await.map_err(|e| {
+        error!("Failed to parse bank API response: {:?}", e);
+        LookupFailure::Fatal(AppError::Reqwest(e))
+    })?;
+
+    Ok(bank_response.data)
+}
@@ -1,6 +1,10 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Algorithm, Argon2, Params, Version,
+};
 use axum::{
-    extract::{Request, State},
-    http::{header, StatusCode},
+    extract::{ConnectInfo, DefaultBodyLimit, Multipart, Request, State},
+    http::{header, StatusCode, Uri},
     middleware::{self, Next},
     response::{IntoResponse, Response},
     routing::{get, post},
@@ -8,13 +12,18 @@ use axum::{
 };
 use chrono::{Duration, Utc};
 use config::{Config, ConfigError, Environment, File};
+use dashmap::DashMap;
+use image::{imageops::FilterType, DynamicImage, GenericImageView, ImageReader, Limits};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use reqwest::Client as ReqwestClient;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use sqids::Sqids;
 use sqlx::{postgres::PgPoolOptions, PgPool};
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use thiserror::Error;
 use tracing::{error, info, instrument};
 use tracing_subscriber;
@@ -28,6 +37,8 @@ use utoipa::{
 };
 use utoipa_swagger_ui::SwaggerUi;
 
+mod bank_lookup;
+
 // --- 1. Production Configuration ---
 
 #[derive(Debug, Deserialize, Clone)]
@@ -40,11 +51,32 @@ struct JwtSettings {
     secret: String,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+struct AvatarSettings {
+    max_size_bytes: usize,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct PublicIdSettings {
+    alphabet: String,
+    min_length: u8,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct RateLimitSettings {
+    max_requests: u32,
+    window_secs: i64,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 struct Settings {
     server_address: String,
     database: DatabaseSettings,
     jwt: JwtSettings,
+    avatar: AvatarSettings,
+    public_id: PublicIdSettings,
+    rate_limit: RateLimitSettings,
+    bank_lookup: bank_lookup::BankLookupSettings,
 }
 
 impl Settings {
@@ -72,16 +104,25 @@ impl Settings {
     paths(
         register_user_handler,
         login_handler,
-        user_info_handler
+        refresh_handler,
+        logout_handler,
+        introspect_handler,
+        user_info_handler,
+        upload_avatar_handler,
+        get_avatar_handler
     ),
     components(
         schemas(
             RegisterUserPayload,
             LoginPayload,
+            RefreshPayload,
+            IntrospectPayload,
+            IntrospectResponse,
             MaskedUserInfo,
             User,
             ErrorResponse,
             LoginResponse,
+            RefreshResponse,
             RegisterSuccessResponse
         )
     ),
@@ -117,6 +158,76 @@ fn mask_account_number(account_number: &str) -> String {
     }
 }
 
+/// Hashes a plaintext password into an Argon2id PHC string using a fresh random salt.
+fn hash_password(password: &str) -> Result<String, AppError> {
+    let salt = SaltString::generate(&mut OsRng);
+    let params = Params::new(19456, 2, 1, None).map_err(|e| {
+        error!("Invalid Argon2 params: {:?}", e);
+        AppError::Internal
+    })?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+    argon2
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| {
+            error!("Password hashing failed: {:?}", e);
+            AppError::Internal
+        })
+}
+
+/// Verifies a plaintext password against a stored Argon2 PHC hash.
+fn verify_password(password: &str, password_hash: &str) -> Result<(), AppError> {
+    let parsed_hash = PasswordHash::new(password_hash).map_err(|e| {
+        error!("Stored password hash is malformed: {:?}", e);
+        AppError::InvalidCredentials
+    })?;
+
+    Argon2::default()
+        .verify_password(password.as_bytes(), &parsed_hash)
+        .map_err(|_| AppError::InvalidCredentials)
+}
+
+/// A PHC hash with no corresponding account, hashed once at startup so that logging in with a
+/// nonexistent account number still pays the same Argon2 cost as a real one. Without this, the
+/// time to reject a login leaks whether the account number exists at all.
+static DUMMY_PASSWORD_HASH: Lazy<String> = Lazy::new(|| {
+    hash_password("correct horse battery staple placeholder password")
+        .expect("hashing the dummy password must succeed")
+});
+
+const AVATAR_SIZE: u32 = 256;
+// Bounds applied to the *decoded* image, independent of the upload's encoded byte size, so a
+// small file that decompresses into a huge bitmap (a "decompression bomb") can't exhaust memory.
+const MAX_AVATAR_INPUT_DIMENSION: u32 = 4096;
+const MAX_AVATAR_DECODE_ALLOC_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Center-crops `img` to a square and resizes it to a fixed `AVATAR_SIZE` square thumbnail
+/// (smaller images are scaled up).
+fn resize_avatar(img: &DynamicImage) -> DynamicImage {
+    let (width, height) = img.dimensions();
+    let side = width.min(height);
+    let x = (width - side) / 2;
+    let y = (height - side) / 2;
+
+    img.crop_imm(x, y, side, side)
+        .resize_exact(AVATAR_SIZE, AVATAR_SIZE, FilterType::Lanczos3)
+}
+
+/// Encodes a user's `sequence_id` into their short, URL-safe public identifier.
+fn encode_public_id(sqids: &Sqids, sequence_id: i64) -> Result<String, AppError> {
+    sqids.encode(&[sequence_id as u64]).map_err(|e| {
+        error!("Failed to encode public id: {:?}", e);
+        AppError::Internal
+    })
+}
+
+/// Decodes a public identifier back to the internal `sequence_id`, e.g. to resolve `GET /users/{public_id}`.
+#[allow(dead_code)]
+fn decode_public_id(sqids: &Sqids, public_id: &str) -> Option<i64> {
+    sqids.decode(public_id).first().map(|&v| v as i64)
+}
+
 // --- 4. Main Application State & Setup ---
 
 #[derive(Clone)]
@@ -124,6 +235,16 @@ struct AppState {
     db_pool: PgPool,
     http_client: ReqwestClient,
     settings: Settings,
+    sqids: Arc<Sqids>,
+    rate_limiter: Arc<DashMap<IpAddr, RateLimitEntry>>,
+    rate_limiter_requests_since_sweep: Arc<AtomicU64>,
+    bank_lookup_cache: Arc<bank_lookup::BankLookupCache>,
+}
+
+/// Fixed-window rate limit bookkeeping for a single client IP.
+struct RateLimitEntry {
+    count: u32,
+    window_start: chrono::DateTime<Utc>,
 }
 
 // Use the default multi-threaded runtime for production performance.
@@ -147,20 +268,48 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("[SUCCESS] Database pool created successfully.");
 
     info!("[STARTUP] Creating HTTP client...");
-    let http_client = ReqwestClient::new();
+    let http_client = bank_lookup::build_client(&settings.bank_lookup)?;
     info!("[SUCCESS] HTTP client created.");
 
+    info!("[STARTUP] Building public ID encoder...");
+    let sqids = Sqids::builder()
+        .alphabet(settings.public_id.alphabet.chars().collect())
+        .min_length(settings.public_id.min_length)
+        .build()
+        .map_err(|e| format!("Invalid public ID alphabet configuration: {}", e))?;
+    info!("[SUCCESS] Public ID encoder built.");
+
+    Lazy::force(&DUMMY_PASSWORD_HASH);
+
     let app_state = AppState {
         db_pool: pool,
         http_client,
         settings: settings.clone(),
+        sqids: Arc::new(sqids),
+        rate_limiter: Arc::new(DashMap::new()),
+        rate_limiter_requests_since_sweep: Arc::new(AtomicU64::new(0)),
+        bank_lookup_cache: Arc::new(DashMap::new()),
     };
 
     info!("[STARTUP] Building application routes...");
     let app = Router::new()
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
-        .route("/register", post(register_user_handler))
-        .route("/login", post(login_handler))
+        .route(
+            "/register",
+            post(register_user_handler).route_layer(middleware::from_fn_with_state(
+                app_state.clone(),
+                rate_limit_middleware,
+            )),
+        )
+        .route(
+            "/login",
+            post(login_handler).route_layer(middleware::from_fn_with_state(
+                app_state.clone(),
+                rate_limit_middleware,
+            )),
+        )
+        .route("/auth/refresh", post(refresh_handler))
+        .route("/auth/introspect", post(introspect_handler))
         .route(
             "/auth/info",
             get(user_info_handler).route_layer(middleware::from_fn_with_state(
@@ -168,6 +317,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 auth_middleware,
             )),
         )
+        .route(
+            "/auth/logout",
+            post(logout_handler).route_layer(middleware::from_fn_with_state(
+                app_state.clone(),
+                auth_middleware,
+            )),
+        )
+        .route(
+            "/auth/avatar",
+            get(get_avatar_handler)
+                .post(upload_avatar_handler)
+                // Reject oversized uploads while the body is being streamed in, instead of
+                // buffering the whole thing before checking its length.
+                .layer(DefaultBodyLimit::max(settings.avatar.max_size_bytes))
+                .route_layer(middleware::from_fn_with_state(
+                    app_state.clone(),
+                    auth_middleware,
+                )),
+        )
+        .fallback(not_found_handler)
         .with_state(app_state);
     info!("[SUCCESS] Application routes built.");
 
@@ -175,7 +344,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("[STARTUP] Binding server to address: {}", addr);
     let listener = tokio::net::TcpListener::bind(addr).await?;
     info!("[SUCCESS] Server bound. Starting to listen for connections...");
-    axum::serve(listener, app.into_make_service()).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -194,8 +367,18 @@ enum AppError {
     InvalidCredentials,
     #[error("Authentication failed: {0}")]
     AuthError(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
     #[error("Conflict: {0}")]
     Conflict(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Rate limit exceeded, retry after {0}s")]
+    RateLimited(i64),
+    #[error("Upstream service unavailable: {0}")]
+    UpstreamUnavailable(String),
     #[error("Internal server error")]
     Internal,
 }
@@ -236,7 +419,22 @@ impl IntoResponse for AppError {
             }
             AppError::InvalidCredentials => (StatusCode::UNAUTHORIZED, "Invalid credentials".to_string()),
             AppError::AuthError(msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
+            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg.clone()),
+            AppError::PayloadTooLarge(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg.clone()),
             AppError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
+            AppError::RateLimited(retry_after) => {
+                let body = Json(ErrorResponse {
+                    error: "Too many requests. Please try again later.".to_string(),
+                });
+                return (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(header::RETRY_AFTER, retry_after.to_string())],
+                    body,
+                )
+                    .into_response();
+            }
+            AppError::UpstreamUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg.clone()),
             AppError::Internal => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "An internal error occurred".to_string(),
@@ -257,21 +455,28 @@ struct RegisterUserPayload {
     account_number: String,
     #[validate(regex(path = "*IFSC_REGEX", message = "Invalid IFSC code format."))]
     ifsc: String,
+    #[validate(length(min = 8, message = "Password must be at least 8 characters."))]
+    password: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 struct LoginPayload {
     account_number: String,
-    ifsc: String,
+    password: String,
 }
 
 #[derive(Debug, Serialize, sqlx::FromRow, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 struct User {
     id: Uuid,
+    /// Per-user monotonic integer backing the public, Sqids-encoded identifier.
+    #[serde(skip_serializing)]
+    sequence_id: i64,
     account_number: String,
     ifsc_code: String,
+    #[serde(skip_serializing)]
+    password_hash: String,
     bank_name: String,
     branch: String,
     address: Option<String>,
@@ -279,12 +484,18 @@ struct User {
     state_code: Option<String>,
     routing_no: Option<String>,
     created_at: chrono::DateTime<Utc>,
+    #[serde(skip_serializing)]
+    session_epoch: chrono::DateTime<Utc>,
+    #[serde(skip_serializing)]
+    avatar: Option<Vec<u8>>,
+    #[serde(skip_serializing)]
+    avatar_content_type: Option<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 struct MaskedUserInfo {
-    id: Uuid,
+    public_id: String,
     masked_account_number: String,
     ifsc_code: String,
     bank_name: String,
@@ -295,53 +506,104 @@ struct MaskedUserInfo {
     routing_no: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
-struct BankApiData {
-    bank_name: String,
-    bank_branch_name: String,
-    address: String,
-    city_and_pincode: String,
-    country_code: String,
-    network_type: String,
-    routing_no: String,
-    state_code: String,
+struct LoginResponse {
+    token: String,
+    refresh_token: String,
 }
 
-#[derive(Debug, Deserialize)]
-struct BankApiResponse {
-    data: BankApiData,
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct RefreshPayload {
+    refresh_token: String,
 }
 
 #[derive(Serialize, ToSchema)]
-struct LoginResponse {
+struct RefreshResponse {
     token: String,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+struct IntrospectPayload {
+    token: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct IntrospectResponse {
+    active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scopes: Option<Vec<String>>,
+}
+
 #[derive(Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 struct RegisterSuccessResponse {
     message: String,
-    user_id: Uuid,
+    public_id: String,
 }
 
 // --- 7. Authentication (JWT) ---
 
-#[derive(Debug, Serialize, Deserialize)]
+const ACCESS_TOKEN_TTL: Duration = Duration::minutes(15);
+const REFRESH_TOKEN_TTL: Duration = Duration::days(30);
+
+/// Scopes granted to every user today; once per-user grants exist this becomes a DB lookup.
+const GRANTED_SCOPES: &[&str] = &["account:read", "account:write"];
+
+fn granted_scopes() -> Vec<String> {
+    GRANTED_SCOPES.iter().map(|s| s.to_string()).collect()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum TokenType {
+    Access,
+    Refresh,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 struct Claims {
     sub: Uuid,
     exp: i64,
+    /// Unix timestamp of the user's `session_epoch` at the moment this token was issued.
+    epoch: i64,
+    typ: TokenType,
+    scopes: Vec<String>,
+}
+
+/// Returns `AppError::Forbidden` if `claims` was not granted `scope`.
+fn require_scope(claims: &Claims, scope: &str) -> Result<(), AppError> {
+    if claims.scopes.iter().any(|s| s == scope) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!("Missing required scope: {}", scope)))
+    }
 }
 
-fn create_jwt(user_id: Uuid, jwt_secret: &str) -> Result<String, AppError> {
+fn create_jwt(
+    user_id: Uuid,
+    session_epoch: chrono::DateTime<Utc>,
+    ttl: Duration,
+    typ: TokenType,
+    scopes: Vec<String>,
+    jwt_secret: &str,
+) -> Result<String, AppError> {
     let expiration = Utc::now()
-        .checked_add_signed(Duration::hours(24))
+        .checked_add_signed(ttl)
         .expect("Failed to calculate expiration")
         .timestamp();
 
     let claims = Claims {
         sub: user_id,
         exp: expiration,
+        epoch: session_epoch.timestamp(),
+        typ,
+        scopes,
     };
 
     jsonwebtoken::encode(
@@ -391,17 +653,86 @@ async fn auth_middleware(
 
     let claims = decode_jwt(token, &state.settings.jwt.secret)?;
 
+    if claims.typ != TokenType::Access {
+        return Err(AppError::AuthError("Access token required".to_string()));
+    }
+
     let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
         .bind(claims.sub)
         .fetch_optional(&state.db_pool)
         .await?
         .ok_or_else(|| AppError::AuthError("User from token not found".to_string()))?;
 
+    if claims.epoch < user.session_epoch.timestamp() {
+        return Err(AppError::AuthError("Token has been revoked".to_string()));
+    }
+
     req.extensions_mut().insert(user);
+    req.extensions_mut().insert(claims);
 
     Ok(next.run(req).await)
 }
 
+// How many requests pass through the rate limiter between stale-entry eviction sweeps.
+const RATE_LIMIT_SWEEP_INTERVAL: u64 = 256;
+
+/// Fixed-window rate limit keyed by client IP, applied to the public auth endpoints.
+#[instrument(skip_all)]
+async fn rate_limit_middleware(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let ip = addr.ip();
+    let now = Utc::now();
+    let window = Duration::seconds(state.settings.rate_limit.window_secs);
+
+    let retry_after = {
+        let mut entry = state.rate_limiter.entry(ip).or_insert_with(|| RateLimitEntry {
+            count: 0,
+            window_start: now,
+        });
+
+        if now - entry.window_start >= window {
+            entry.count = 0;
+            entry.window_start = now;
+        }
+
+        entry.count += 1;
+
+        if entry.count > state.settings.rate_limit.max_requests {
+            Some((window - (now - entry.window_start)).num_seconds().max(0))
+        } else {
+            None
+        }
+    };
+
+    // Opportunistically evict stale entries so the map doesn't grow unbounded with one entry
+    // per distinct source IP ever seen. A full `retain` scan takes a write lock on every shard,
+    // so it only runs once every `RATE_LIMIT_SWEEP_INTERVAL` requests rather than on each one.
+    let requests_since_sweep = state
+        .rate_limiter_requests_since_sweep
+        .fetch_add(1, Ordering::Relaxed)
+        + 1;
+    if requests_since_sweep >= RATE_LIMIT_SWEEP_INTERVAL {
+        state.rate_limiter_requests_since_sweep.store(0, Ordering::Relaxed);
+        let stale_after = Duration::seconds(state.settings.rate_limit.window_secs * 2);
+        state.rate_limiter.retain(|_, entry| now - entry.window_start < stale_after);
+    }
+
+    if let Some(retry_after) = retry_after {
+        return Err(AppError::RateLimited(retry_after));
+    }
+
+    Ok(next.run(req).await)
+}
+
+/// Renders unmatched routes with the same `ErrorResponse` JSON shape as every other error.
+async fn not_found_handler(uri: Uri) -> AppError {
+    AppError::NotFound(format!("No route found for {}", uri))
+}
+
 // --- 8. API Handlers ---
 
 /// Register a new user
@@ -435,41 +766,29 @@ async fn register_user_handler(
         return Err(AppError::Conflict("Account number already registered.".to_string()));
     }
 
-    info!("Attempting to call external API for IFSC: {}", payload.ifsc);
-    let api_url = "https://api.bulkpe.in/api/validateIFSCStatic";
-
-    let api_response = state
-        .http_client
-        .post(api_url)
-        .json(&json!({ "ifsc": &payload.ifsc }))
-        .send()
-        .await?;
-
-
-    if !api_response.status().is_success() {
-        let status = api_response.status();
-        let error_body = api_response.text().await.unwrap_or_default();
-        error!(
-            "External API returned a non-success status: {}. Body: {}",
-            status, error_body
-        );
-        return Err(AppError::Validation("The provided IFSC code is not valid or could not be verified by the bank API.".to_string()));
-    }
-
-    let bank_response: BankApiResponse = api_response.json().await?;
-    let bank_data = bank_response.data;
+    info!("Looking up bank details for IFSC: {}", payload.ifsc);
+    let bank_data = bank_lookup::lookup_ifsc(
+        &state.http_client,
+        &state.bank_lookup_cache,
+        &state.settings.bank_lookup,
+        &payload.ifsc,
+    )
+    .await?;
     info!("Successfully fetched bank details for {}: {}", payload.ifsc, bank_data.bank_name);
 
+    let password_hash = hash_password(&payload.password)?;
+
     let new_user = sqlx::query_as::<_, User>(
         r#"
-        INSERT INTO users (id, account_number, ifsc_code, bank_name, branch, address, city, state_code, routing_no)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        INSERT INTO users (id, account_number, ifsc_code, password_hash, bank_name, branch, address, city, state_code, routing_no)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
         RETURNING *
         "#,
     )
         .bind(Uuid::new_v4())
         .bind(&payload.account_number)
         .bind(&payload.ifsc)
+        .bind(&password_hash)
         .bind(&bank_data.bank_name)
         .bind(&bank_data.bank_branch_name)
         .bind(&bank_data.address)
@@ -489,11 +808,13 @@ async fn register_user_handler(
 
     info!("New user registered with ID: {}", new_user.id);
 
+    let public_id = encode_public_id(&state.sqids, new_user.sequence_id)?;
+
     Ok((
         StatusCode::CREATED,
         Json(RegisterSuccessResponse {
             message: "User registered successfully.".to_string(),
-            user_id: new_user.id,
+            public_id,
         }),
     ))
 }
@@ -518,18 +839,164 @@ async fn login_handler(
     let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE account_number = $1")
         .bind(&payload.account_number)
         .fetch_optional(&state.db_pool)
-        .await?
-        .ok_or(AppError::InvalidCredentials)?;
+        .await?;
 
-    if user.ifsc_code != payload.ifsc {
+    let Some(user) = user else {
+        // Still run a full Argon2 verify against a dummy hash so a nonexistent account number
+        // takes the same time to reject as a wrong password for a real one.
+        let _ = verify_password(&payload.password, &DUMMY_PASSWORD_HASH);
         return Err(AppError::InvalidCredentials);
-    }
+    };
 
-    let token = create_jwt(user.id, &state.settings.jwt.secret)?;
+    verify_password(&payload.password, &user.password_hash)?;
+
+    let token = create_jwt(
+        user.id,
+        user.session_epoch,
+        ACCESS_TOKEN_TTL,
+        TokenType::Access,
+        granted_scopes(),
+        &state.settings.jwt.secret,
+    )?;
+    let refresh_token = create_jwt(
+        user.id,
+        user.session_epoch,
+        REFRESH_TOKEN_TTL,
+        TokenType::Refresh,
+        granted_scopes(),
+        &state.settings.jwt.secret,
+    )?;
 
     info!("User {} logged in successfully.", user.id);
 
-    Ok(Json(LoginResponse { token }))
+    Ok(Json(LoginResponse { token, refresh_token }))
+}
+
+/// Mint a fresh access token from a valid refresh token
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    request_body = RefreshPayload,
+    responses(
+        (status = 200, description = "Access token refreshed", body = RefreshResponse),
+        (status = 401, description = "Invalid or expired refresh token", body = ErrorResponse)
+    )
+)]
+#[axum::debug_handler]
+#[instrument(skip_all)]
+async fn refresh_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshPayload>,
+) -> Result<impl IntoResponse, AppError> {
+    let claims = decode_jwt(&payload.refresh_token, &state.settings.jwt.secret)?;
+
+    if claims.typ != TokenType::Refresh {
+        return Err(AppError::AuthError("Refresh token required".to_string()));
+    }
+
+    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+        .bind(claims.sub)
+        .fetch_optional(&state.db_pool)
+        .await?
+        .ok_or_else(|| AppError::AuthError("User from token not found".to_string()))?;
+
+    if claims.epoch < user.session_epoch.timestamp() {
+        return Err(AppError::AuthError("Token has been revoked".to_string()));
+    }
+
+    let token = create_jwt(
+        user.id,
+        user.session_epoch,
+        ACCESS_TOKEN_TTL,
+        TokenType::Access,
+        claims.scopes,
+        &state.settings.jwt.secret,
+    )?;
+
+    info!("Refreshed access token for user {}.", user.id);
+
+    Ok(Json(RefreshResponse { token }))
+}
+
+/// Introspect a token per RFC 7662, for other services to verify it
+#[utoipa::path(
+    post,
+    path = "/auth/introspect",
+    request_body = IntrospectPayload,
+    responses(
+        (status = 200, description = "Introspection result", body = IntrospectResponse)
+    )
+)]
+#[axum::debug_handler]
+#[instrument(skip_all)]
+async fn introspect_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<IntrospectPayload>,
+) -> Result<impl IntoResponse, AppError> {
+    let inactive = IntrospectResponse {
+        active: false,
+        public_id: None,
+        exp: None,
+        scopes: None,
+    };
+
+    let Ok(claims) = decode_jwt(&payload.token, &state.settings.jwt.secret) else {
+        return Ok(Json(inactive));
+    };
+
+    if claims.typ != TokenType::Access {
+        return Ok(Json(inactive));
+    }
+
+    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+        .bind(claims.sub)
+        .fetch_optional(&state.db_pool)
+        .await?;
+
+    let Some(user) = user else {
+        return Ok(Json(inactive));
+    };
+
+    if claims.epoch < user.session_epoch.timestamp() {
+        return Ok(Json(inactive));
+    }
+
+    let public_id = encode_public_id(&state.sqids, user.sequence_id)?;
+
+    Ok(Json(IntrospectResponse {
+        active: true,
+        public_id: Some(public_id),
+        exp: Some(claims.exp),
+        scopes: Some(claims.scopes),
+    }))
+}
+
+/// Log out, revoking all outstanding tokens for the current user
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Logged out successfully"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse)
+    )
+)]
+#[axum::debug_handler]
+#[instrument(skip_all)]
+async fn logout_handler(
+    State(state): State<AppState>,
+    axum::Extension(user): axum::Extension<User>,
+) -> Result<impl IntoResponse, AppError> {
+    sqlx::query("UPDATE users SET session_epoch = now() WHERE id = $1")
+        .bind(user.id)
+        .execute(&state.db_pool)
+        .await?;
+
+    info!("User {} logged out; session epoch bumped.", user.id);
+
+    Ok(Json(json!({ "message": "Logged out successfully." })))
 }
 
 
@@ -542,16 +1009,21 @@ async fn login_handler(
     ),
     responses(
         (status = 200, description = "User info retrieved successfully", body = MaskedUserInfo),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     )
 )]
 #[axum::debug_handler]
 #[instrument(skip_all)]
 async fn user_info_handler(
+    State(state): State<AppState>,
     axum::Extension(user): axum::Extension<User>,
+    axum::Extension(claims): axum::Extension<Claims>,
 ) -> Result<impl IntoResponse, AppError> {
+    require_scope(&claims, "account:read")?;
+
     let masked_info = MaskedUserInfo {
-        id: user.id,
+        public_id: encode_public_id(&state.sqids, user.sequence_id)?,
         masked_account_number: mask_account_number(&user.account_number),
         ifsc_code: user.ifsc_code,
         bank_name: user.bank_name,
@@ -566,3 +1038,144 @@ async fn user_info_handler(
 
     Ok(Json(masked_info))
 }
+
+/// Upload and resize the authenticated user's profile avatar
+#[utoipa::path(
+    post,
+    path = "/auth/avatar",
+    security(
+        ("bearer_auth" = [])
+    ),
+    request_body(content = Vec<u8>, content_type = "multipart/form-data"),
+    responses(
+        (status = 204, description = "Avatar uploaded successfully"),
+        (status = 400, description = "Invalid or missing image", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 413, description = "Avatar exceeds the configured size limit", body = ErrorResponse)
+    )
+)]
+#[axum::debug_handler]
+#[instrument(skip_all)]
+async fn upload_avatar_handler(
+    State(state): State<AppState>,
+    axum::Extension(user): axum::Extension<User>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    let field = multipart
+        .next_field()
+        .await
+        .map_err(|e| {
+            error!("Failed to read multipart upload: {:?}", e);
+            AppError::Validation("Invalid multipart upload".to_string())
+        })?
+        .ok_or_else(|| AppError::Validation("No avatar file provided".to_string()))?;
+
+    let content_type = field.content_type().unwrap_or_default().to_string();
+    if content_type != "image/png" && content_type != "image/jpeg" {
+        return Err(AppError::Validation(
+            "Avatar must be a PNG or JPEG image".to_string(),
+        ));
+    }
+
+    // `DefaultBodyLimit` on this route already rejects bodies over `max_size_bytes` while
+    // they're being streamed in, so this never buffers an oversized upload in full; the
+    // length check below is defense in depth in case that layer is ever removed or misconfigured.
+    let data = field.bytes().await.map_err(|e| {
+        if e.to_string().to_lowercase().contains("length limit exceeded") {
+            AppError::PayloadTooLarge(format!(
+                "Avatar must be under {} bytes",
+                state.settings.avatar.max_size_bytes
+            ))
+        } else {
+            error!("Failed to read avatar bytes: {:?}", e);
+            AppError::Validation("Invalid multipart upload".to_string())
+        }
+    })?;
+
+    if data.len() > state.settings.avatar.max_size_bytes {
+        return Err(AppError::PayloadTooLarge(format!(
+            "Avatar must be under {} bytes",
+            state.settings.avatar.max_size_bytes
+        )));
+    }
+
+    // Bound the decoder itself: a tiny file can still decode into an enormous bitmap, so cap
+    // both the pixel dimensions and the total allocation the decoder is allowed to make.
+    let mut reader = ImageReader::new(std::io::Cursor::new(&data))
+        .with_guessed_format()
+        .map_err(|e| {
+            error!("Failed to detect avatar image format: {:?}", e);
+            AppError::Validation("Could not decode image".to_string())
+        })?;
+    let mut limits = Limits::no_limits();
+    limits.max_image_width = Some(MAX_AVATAR_INPUT_DIMENSION);
+    limits.max_image_height = Some(MAX_AVATAR_INPUT_DIMENSION);
+    limits.max_alloc = Some(MAX_AVATAR_DECODE_ALLOC_BYTES);
+    reader.limits(limits);
+
+    let decoded = reader.decode().map_err(|e| {
+        error!("Failed to decode avatar image: {:?}", e);
+        AppError::Validation("Could not decode image".to_string())
+    })?;
+
+    let thumbnail = resize_avatar(&decoded);
+
+    let mut png_bytes = Vec::new();
+    thumbnail
+        .write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageFormat::Png)
+        .map_err(|e| {
+            error!("Failed to encode avatar thumbnail: {:?}", e);
+            AppError::Internal
+        })?;
+
+    sqlx::query("UPDATE users SET avatar = $1, avatar_content_type = $2 WHERE id = $3")
+        .bind(&png_bytes)
+        .bind("image/png")
+        .bind(user.id)
+        .execute(&state.db_pool)
+        .await?;
+
+    info!("Updated avatar for user {}.", user.id);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Fetch the authenticated user's profile avatar
+#[utoipa::path(
+    get,
+    path = "/auth/avatar",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Avatar image bytes", content_type = "image/png"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "No avatar set for this user", body = ErrorResponse)
+    )
+)]
+#[axum::debug_handler]
+#[instrument(skip_all)]
+async fn get_avatar_handler(
+    State(state): State<AppState>,
+    axum::Extension(user): axum::Extension<User>,
+) -> Result<impl IntoResponse, AppError> {
+    let row = sqlx::query_as::<_, (Option<Vec<u8>>, Option<String>)>(
+        "SELECT avatar, avatar_content_type FROM users WHERE id = $1",
+    )
+        .bind(user.id)
+        .fetch_one(&state.db_pool)
+        .await?;
+
+    match row {
+        (Some(bytes), Some(content_type)) => {
+            Ok(([(header::CONTENT_TYPE, content_type)], bytes).into_response())
+        }
+        _ => Ok((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "No avatar set for this user.".to_string(),
+            }),
+        )
+            .into_response()),
+    }
+}